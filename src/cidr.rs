@@ -0,0 +1,52 @@
+//! Expansion of CIDR notation (e.g. `"192.168.1.0/24"`) into the individual host addresses
+//! it covers, used by [`Scanner::scan_cidr`](crate::Scanner::scan_cidr) to sweep a whole LAN
+//! segment instead of a single target.
+
+use std::net::Ipv4Addr;
+
+/// The shortest prefix [`expand`] will materialize, capping a single block at 65,536 addresses (65,534 hosts).
+/// Anything shorter (e.g. `/8`'s ~16.7M hosts, or `/0`'s ~4.29B) would eagerly build a `Vec`
+/// far larger than any realistic LAN sweep needs before a single host gets scanned.
+const MIN_PREFIX: u32 = 16;
+
+/// Expands a CIDR block such as `"192.168.1.0/24"` into its individual host addresses,
+/// excluding the network and broadcast addresses for subnets that have them (i.e. prefix
+/// lengths of `/30` or shorter).
+///
+/// # Panics
+///
+/// Panics if `cidr` is not in `ip/prefix` form with a valid IPv4 address and a prefix length
+/// of `0..=32`, or if the prefix is shorter than [`MIN_PREFIX`] (i.e. covers more than 65,536
+/// hosts).
+pub(crate) fn expand(cidr: &str) -> Vec<Ipv4Addr> {
+    let (base, prefix) = cidr.split_once('/').expect("invalid CIDR notation");
+    let base: Ipv4Addr = base.parse().expect("invalid ip address");
+    let prefix: u32 = prefix.parse().expect("invalid prefix length");
+    assert!(prefix <= 32, "prefix length must be 0..=32");
+    assert!(
+        prefix >= MIN_PREFIX,
+        "prefix must be /{MIN_PREFIX} or longer (got /{prefix}), which would expand to more \
+         than 65,536 hosts"
+    );
+
+    let (start, end) = host_range(base, prefix);
+    (start..end).map(|addr| Ipv4Addr::from(addr as u32)).collect()
+}
+
+/// Computes the `[start, end)` range of host addresses (as `u32`s widened to `u64`) covered by
+/// `base/prefix`, excluding the network and broadcast addresses for subnets that have them.
+///
+/// Split out of [`expand`] so the range arithmetic can be checked without materializing a
+/// `Vec` that, for short prefixes, would hold billions of addresses.
+pub(crate) fn host_range(base: Ipv4Addr, prefix: u32) -> (u64, u64) {
+    let host_bits = 32 - prefix;
+    let mask = if prefix == 0 { 0 } else { !0u32 << host_bits };
+    let network = u64::from(u32::from(base) & mask);
+    let count = 1u64 << host_bits; // u64 so host_bits == 32 (prefix 0) doesn't overflow
+
+    if host_bits >= 2 {
+        (network + 1, network + count - 1) // exclude network and broadcast addresses
+    } else {
+        (network, network + count)
+    }
+}
@@ -0,0 +1,18 @@
+//! Curated list of the most commonly open TCP ports, in roughly the same spirit as the
+//! nmap-services "top ports" list. Used by [`Scanner::scan_common`](crate::Scanner::scan_common)
+//! and [`Scanner::scan_top`](crate::Scanner::scan_top) to get useful results in a fraction of
+//! the time a full 0..=65535 scan takes.
+
+/// The most commonly open TCP ports, ordered roughly by how frequently they're found open.
+pub(crate) const COMMON_PORTS: &[u16] = &[
+    80, 23, 443, 21, 22, 25, 3389, 110, 445, 139, 143, 53, 135, 3306, 8080, 1723, 111, 995, 993,
+    5900, 1025, 587, 8888, 199, 1720, 465, 548, 113, 81, 6001, 10000, 514, 5060, 179, 1026, 2000,
+    8443, 8000, 32768, 554, 26, 1433, 49152, 2001, 515, 8008, 49154, 1027, 5666, 646, 5000, 5631,
+    631, 49153, 8081, 2049, 88, 79, 5800, 106, 2121, 1110, 49155, 6000, 513, 990, 5357, 427,
+    49156, 543, 544, 5101, 144, 7, 389, 8009, 3128, 444, 9999, 5009, 7070, 5190, 3000, 5432, 1900,
+    3986, 13, 1029, 9, 5051, 6646, 49157, 1028, 873, 1755, 2717, 4899, 9100, 119, 37, 1000, 3001,
+    5001, 82, 10001, 1030, 9090, 2107, 1024, 2103, 6004, 1031, 1032, 200, 6005, 6006, 6002, 6003,
+    4001, 8010, 3689, 3690, 900, 8002, 7000, 2701, 9102, 9101, 6666, 1521, 27017, 27018, 6379,
+    11211, 5984, 9200, 5985, 5986, 47001, 2222, 2082, 2083, 2086, 2087, 2095, 2096, 9000, 9001,
+    9043, 9060, 50000, 50070, 8161, 61616, 1099,
+];
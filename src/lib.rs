@@ -12,28 +12,256 @@
 //! async fn main() {
 //!     let ip = "127.0.0.1".to_string();
 //!     let ports = Scanner::scan(ip).await;
-//!     println!("Current open ports {:?}"), ports;
+//!     println!("Current open ports {:?}", ports);
 //! }
 //! ```
 
 
-use tokio::net::TcpStream;
+mod cidr;
+mod common_ports;
+
+use std::io;
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+
+use futures::stream::{self, StreamExt};
+use tokio::net::{TcpStream, UdpSocket};
 use tokio::time::{self, Duration};
 
+use common_ports::COMMON_PORTS;
+
 const MAX_PORT: u16 = 65535;
 
-/// A simple asynchronous port scanner.
+/// The number of ports scanned concurrently by [`Scanner::scan`].
+const DEFAULT_CONCURRENCY: usize = 1000;
+
+/// The number of hosts scanned concurrently by [`Scanner::scan_hosts`].
+const DEFAULT_HOST_CONCURRENCY: usize = 32;
+
+/// The default per-connection timeout used by the static `Scanner::scan*` helpers.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A configurable asynchronous port scanner.
 ///
-/// This struct provides functionality to scan all ports (0 to 65535) on a given IP address
-/// to determine which ones are open and accepting TCP connections.
-pub struct Scanner;
+/// Besides the static [`Scanner::scan`] helpers, which scan with sane defaults, a `Scanner`
+/// instance carries its own per-connection `timeout`, a concurrency limit, a default port
+/// range, and an optional batch size, set up through a chainable builder so callers aren't
+/// stuck with the hard-coded 1-second timeout and full `0..=65535` range. The static helpers
+/// are thin wrappers around a default-configured `Scanner` so every scan mode shares the same
+/// connect-check and concurrency machinery.
+pub struct Scanner {
+    timeout: Duration,
+    concurrency: usize,
+    port_range: RangeInclusive<u16>,
+    batch_size: Option<usize>,
+}
 
 impl Scanner {
-    /// Scans through all ports on the specified IP address asynchronously.
+    /// Creates a new `Scanner` with the given per-connection `timeout`.
+    ///
+    /// The concurrency limit defaults to [`DEFAULT_CONCURRENCY`], the port range defaults to
+    /// the full `0..=65535` range, and batching is disabled until
+    /// [`Scanner::with_batch_size`] is called.
+    pub fn new(timeout: Duration) -> Self {
+        Scanner {
+            timeout,
+            concurrency: DEFAULT_CONCURRENCY,
+            port_range: 0..=MAX_PORT,
+            batch_size: None,
+        }
+    }
+
+    /// Sets the maximum number of connection attempts this `Scanner` keeps in flight at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Restricts the default port range scanned by this `Scanner` to `start..=end`.
+    pub fn with_port_range(mut self, start: u16, end: u16) -> Self {
+        self.port_range = start..=end;
+        self
+    }
+
+    /// Sets the chunk size used by [`Scanner::run_batched`] when splitting a port range.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Runs the scan using the port range and batch size configured on this `Scanner`,
+    /// batching automatically if [`Scanner::with_batch_size`] was set.
+    pub async fn run_configured(&self, ip: &str) -> Vec<SocketAddr> {
+        let (start, end) = (*self.port_range.start(), *self.port_range.end());
+        match self.batch_size {
+            Some(batch) => self.run_batched(ip, start, end, batch).await,
+            None => self.run(ip, start, end).await,
+        }
+    }
+
+    /// Scans `start..=end` on `ip` using this scanner's timeout, returning the `SocketAddr`s
+    /// that accepted a connection.
+    ///
+    /// `ip` is handed straight to `TcpStream::connect` rather than pre-parsed, so DNS names
+    /// (e.g. `"localhost"`) work the same way they do for [`Scanner::scan`].
+    pub async fn run(&self, ip: &str, start: u16, end: u16) -> Vec<SocketAddr> {
+        stream::iter(start..=end)
+            .map(|port| async move {
+                match time::timeout(self.timeout, TcpStream::connect((ip, port))).await {
+                    Ok(Ok(stream)) => stream.peer_addr().ok(),
+                    _ => None,
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|result| async move { result })
+            .collect()
+            .await
+    }
+
+    /// Scans `start..=end` on `ip` in fixed-size chunks of `batch` ports at a time, so huge
+    /// ranges don't all have their connections in flight simultaneously.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch` is `0`, since there is no valid chunk size to split the range into, or
+    /// if `batch` is greater than `u16::MAX`, since it would silently truncate when used as a
+    /// port count.
+    pub async fn run_batched(&self, ip: &str, start: u16, end: u16, batch: usize) -> Vec<SocketAddr> {
+        assert!(batch > 0, "batch size must be greater than zero");
+        assert!(
+            batch <= u16::MAX as usize,
+            "batch size must not exceed u16::MAX"
+        );
+        let mut open = Vec::new();
+        let mut chunk_start = start;
+        loop {
+            let chunk_end = chunk_start.saturating_add(batch as u16 - 1).min(end);
+            open.extend(self.run(ip, chunk_start, chunk_end).await);
+            if chunk_end >= end {
+                break;
+            }
+            chunk_start = chunk_end + 1;
+        }
+        open
+    }
+
+    async fn check_port(&self, ip: &str, port: u16) -> bool {
+        matches!(
+            time::timeout(self.timeout, TcpStream::connect((ip, port))).await,
+            Ok(Ok(_))
+        )
+    }
+
+    async fn check_port_state(&self, ip: &str, port: u16) -> PortState {
+        match time::timeout(self.timeout, TcpStream::connect((ip, port))).await {
+            Ok(Ok(_)) => PortState::Open,
+            Ok(Err(err))
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset
+                ) =>
+            {
+                PortState::Closed
+            }
+            Ok(Err(_)) => PortState::Filtered,
+            Err(_) => PortState::Filtered,
+        }
+    }
+
+    async fn check_udp_port_state(&self, ip: &str, port: u16) -> PortState {
+        let socket = match UdpSocket::bind("0.0.0.0:0").await {
+            Ok(socket) => socket,
+            Err(_) => return PortState::Filtered,
+        };
+        if socket.connect((ip, port)).await.is_err() {
+            return PortState::Filtered;
+        }
+        if socket.send(&[]).await.is_err() {
+            return PortState::Filtered;
+        }
+
+        let mut buf = [0u8; 1];
+        match time::timeout(self.timeout, socket.recv(&mut buf)).await {
+            Ok(Ok(_)) => PortState::Open,
+            Ok(Err(err)) if err.kind() == io::ErrorKind::ConnectionRefused => PortState::Closed,
+            Ok(Err(_)) => PortState::OpenFiltered,
+            Err(_) => PortState::OpenFiltered,
+        }
+    }
+
+    /// Scans an arbitrary list of `ports` on `ip`, returning the ones that are open.
+    async fn run_ports(&self, ip: &str, ports: &[u16]) -> Vec<u16> {
+        stream::iter(ports.to_vec())
+            .map(|port| async move {
+                if self.check_port(ip, port).await {
+                    Some(port)
+                } else {
+                    None
+                }
+            })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|result| async move { result })
+            .collect()
+            .await
+    }
+
+    /// Scans only the most commonly open TCP ports instead of the full 0..=65535 range,
+    /// returning useful results in a fraction of the time.
+    pub async fn run_common(&self, ip: &str) -> Vec<u16> {
+        self.run_ports(ip, COMMON_PORTS).await
+    }
+
+    /// Scans the first `n` entries of the common-ports list on `ip`.
+    pub async fn run_top(&self, ip: &str, n: usize) -> Vec<u16> {
+        let ports = &COMMON_PORTS[..n.min(COMMON_PORTS.len())];
+        self.run_ports(ip, ports).await
+    }
+
+    /// Scans `start..=end` on `ip`, classifying each port as open, closed, or filtered
+    /// instead of collapsing every failure into a single "not open" boolean.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(u16, PortState)>` covering every scanned port, in whatever order the
+    /// connection attempts resolve.
+    pub async fn run_detailed(&self, ip: &str, start: u16, end: u16) -> Vec<(u16, PortState)> {
+        self.run_proto(ip, start, end, Protocol::Tcp).await
+    }
+
+    /// Scans `start..=end` on `ip` over the given `protocol`, classifying each port the same
+    /// way [`Scanner::run_detailed`] does.
+    ///
+    /// For [`Protocol::Udp`], a connection refused (surfaced as an ICMP port-unreachable
+    /// error) is reported as [`PortState::Closed`]; silence before the timeout elapses is
+    /// reported as [`PortState::OpenFiltered`], since UDP gives no way to tell an open port
+    /// from a filtered one without a protocol-specific probe.
+    ///
+    /// # Returns
     ///
-    /// This method attempts to connect to each port within the standard range (0 to 65535)
-    /// using a TCP connection. It returns a list of ports that successfully accept the connection,
-    /// indicating these ports are open.
+    /// A `Vec<(u16, PortState)>` covering every scanned port, in whatever order the probes
+    /// resolve.
+    pub async fn run_proto(
+        &self,
+        ip: &str,
+        start: u16,
+        end: u16,
+        protocol: Protocol,
+    ) -> Vec<(u16, PortState)> {
+        stream::iter(start..=end)
+            .map(|port| async move {
+                let state = match protocol {
+                    Protocol::Tcp => self.check_port_state(ip, port).await,
+                    Protocol::Udp => self.check_udp_port_state(ip, port).await,
+                };
+                (port, state)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    /// Scans through all ports on the specified IP address asynchronously, using a
+    /// default-configured `Scanner` (1-second timeout, [`DEFAULT_CONCURRENCY`] in flight).
     ///
     /// # Parameters
     ///
@@ -43,35 +271,165 @@ impl Scanner {
     ///
     /// A `Vec<u16>` containing all open ports found during the scan.
     pub async fn scan(ip: String) -> Vec<u16> {
-        let mut ports: Vec<u16> = Vec::new();
-        let mut tasks = vec![];
+        Scanner::scan_with_concurrency(ip, DEFAULT_CONCURRENCY).await
+    }
 
-        for port in 0..=MAX_PORT {
-            let ip = ip.clone();
-            tasks.push(tokio::spawn(async move {
-                if Scanner::check_port(&ip, port).await {
-                    Some(port)
-                } else {
-                    None
-                }
-            }));
-        }
+    /// Scans through all ports on the specified IP address, allowing at most `concurrency`
+    /// connection attempts to be in flight at once.
+    ///
+    /// Spawning a task per port for the full 0..=65535 range can exhaust the OS's open
+    /// file-descriptor limit, so this bounds the number of concurrent connection checks
+    /// using a `buffer_unordered` stream instead of spawning a task per port.
+    ///
+    /// # Parameters
+    ///
+    /// * `ip`: The IP address to scan, provided as a `String`.
+    /// * `concurrency`: The maximum number of ports to check at the same time.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u16>` containing all open ports found during the scan.
+    pub async fn scan_with_concurrency(ip: String, concurrency: usize) -> Vec<u16> {
+        Scanner::new(DEFAULT_TIMEOUT)
+            .with_concurrency(concurrency)
+            .run(&ip, 0, MAX_PORT)
+            .await
+            .into_iter()
+            .map(|addr| addr.port())
+            .collect()
+    }
 
-        let results = futures::future::join_all(tasks).await;
-        for result in results {
-            if let Ok(Some(port)) = result {
-                ports.push(port);
-            }
-        }
-        ports
+    /// Scans every port in the standard range, classifying each as open, closed, or filtered
+    /// instead of collapsing every failure into a single "not open" boolean. Uses a
+    /// default-configured `Scanner`; see [`Scanner::run_detailed`] for a configurable version.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(u16, PortState)>` covering every scanned port, in whatever order the
+    /// connection attempts resolve.
+    pub async fn scan_detailed(ip: String) -> Vec<(u16, PortState)> {
+        Scanner::new(DEFAULT_TIMEOUT).run_detailed(&ip, 0, MAX_PORT).await
     }
 
-    async fn check_port(ip: &str, port: u16) -> bool {
-        match time::timeout(Duration::from_secs(1), TcpStream::connect((ip, port))).await {
-            Ok(Ok(_)) => true,
-            _ => false,
-        }
+    /// Scans only the most commonly open TCP ports instead of the full 0..=65535 range,
+    /// returning useful results in a fraction of the time. Uses a default-configured
+    /// `Scanner`; see [`Scanner::run_common`] for a configurable version.
+    ///
+    /// # Parameters
+    ///
+    /// * `ip`: The IP address to scan, provided as a `String`.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u16>` containing all open ports found among the common ports.
+    pub async fn scan_common(ip: String) -> Vec<u16> {
+        Scanner::new(DEFAULT_TIMEOUT).run_common(&ip).await
+    }
+
+    /// Scans the first `n` entries of the common-ports list on the specified IP address.
+    /// Uses a default-configured `Scanner`; see [`Scanner::run_top`] for a configurable
+    /// version.
+    ///
+    /// # Parameters
+    ///
+    /// * `ip`: The IP address to scan, provided as a `String`.
+    /// * `n`: How many of the most common ports to scan.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u16>` containing all open ports found among the scanned ports.
+    pub async fn scan_top(ip: String, n: usize) -> Vec<u16> {
+        Scanner::new(DEFAULT_TIMEOUT).run_top(&ip, n).await
+    }
+
+    /// Scans multiple hosts, allowing at most [`DEFAULT_HOST_CONCURRENCY`] hosts and
+    /// `concurrency` connection attempts per host to be in flight at once.
+    ///
+    /// Hosts are scanned through a `buffer_unordered` stream rather than one after another, so
+    /// sweeping a whole LAN segment doesn't take as long as scanning every host in turn.
+    ///
+    /// # Parameters
+    ///
+    /// * `hosts`: The IP addresses to scan.
+    /// * `concurrency`: The maximum number of ports to check at the same time, per host.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(String, Vec<u16>)>` pairing each host with the open ports found on it.
+    pub async fn scan_hosts(hosts: Vec<String>, concurrency: usize) -> Vec<(String, Vec<u16>)> {
+        stream::iter(hosts)
+            .map(|host| async move {
+                let open = Scanner::scan_with_concurrency(host.clone(), concurrency).await;
+                (host, open)
+            })
+            .buffer_unordered(DEFAULT_HOST_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Scans every host in a CIDR block, e.g. `"192.168.1.0/24"`.
+    ///
+    /// The block is expanded into its individual host addresses (excluding the network and
+    /// broadcast addresses for subnets that have them), then scanned with
+    /// [`Scanner::scan_hosts`].
+    ///
+    /// # Parameters
+    ///
+    /// * `block`: The CIDR block to sweep, e.g. `"192.168.1.0/24"`.
+    /// * `concurrency`: The maximum number of ports to check at the same time, per host.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(String, Vec<u16>)>` pairing each host in the block with the open ports found
+    /// on it.
+    pub async fn scan_cidr(block: &str, concurrency: usize) -> Vec<(String, Vec<u16>)> {
+        let hosts = cidr::expand(block)
+            .into_iter()
+            .map(|addr| addr.to_string())
+            .collect();
+        Scanner::scan_hosts(hosts, concurrency).await
     }
+
+    /// Scans every port in the standard range over the given `protocol`, classifying each
+    /// port the same way [`Scanner::scan_detailed`] does. Uses a default-configured
+    /// `Scanner`; see [`Scanner::run_proto`] for a configurable version.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(u16, PortState)>` covering every scanned port, in whatever order the probes
+    /// resolve.
+    pub async fn scan_proto(ip: String, protocol: Protocol) -> Vec<(u16, PortState)> {
+        Scanner::new(DEFAULT_TIMEOUT)
+            .run_proto(&ip, 0, MAX_PORT, protocol)
+            .await
+    }
+}
+
+/// The state of a scanned port, mirroring the open/closed/filtered classification used by
+/// nmap-style scanners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortState {
+    /// A connection was successfully established.
+    Open,
+    /// The connection was actively refused (or reset), meaning something is listening on the
+    /// host but not accepting connections on this port.
+    Closed,
+    /// No response was received before the timeout elapsed, typically indicating a firewall
+    /// is dropping packets rather than the port being explicitly closed.
+    Filtered,
+    /// No response was received before the timeout elapsed on a UDP probe. Unlike TCP,
+    /// silence on UDP is inherently ambiguous: it means the port is either open (and the
+    /// service simply didn't reply to an empty probe) or filtered by a firewall.
+    OpenFiltered,
+}
+
+/// The transport protocol a scan is performed over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// A TCP connect scan.
+    Tcp,
+    /// A UDP probe scan.
+    Udp,
 }
 
 
@@ -112,6 +470,159 @@ mod test {
         });
     }
 
+    /// Tests that a configured `Scanner` finds an open port within a narrow range.
+    #[test]
+    fn test_run_finds_open_port() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let _listener = start_mock_server(3002).await;
+            let scanner = Scanner::new(Duration::from_millis(200));
+            let open = scanner.run("127.0.0.1", 3000, 3005).await;
+            assert!(open.iter().any(|addr| addr.port() == 3002));
+        });
+    }
+
+    /// Tests that `run_batched` still finds an open port when splitting the range into chunks.
+    #[test]
+    fn test_run_batched_finds_open_port() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let _listener = start_mock_server(3006).await;
+            let scanner = Scanner::new(Duration::from_millis(200));
+            let open = scanner.run_batched("127.0.0.1", 3000, 3010, 3).await;
+            assert!(open.iter().any(|addr| addr.port() == 3006));
+        });
+    }
+
+    /// Tests that `run_configured` finds an open port using the port range and batch size set
+    /// through the builder, rather than `run`/`run_batched` called directly with explicit args.
+    #[test]
+    fn test_run_configured_uses_builder_port_range_and_batch_size() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let _listener = start_mock_server(3011).await;
+            let scanner = Scanner::new(Duration::from_millis(200))
+                .with_port_range(3010, 3020)
+                .with_batch_size(3);
+            let open = scanner.run_configured("127.0.0.1").await;
+            assert!(open.iter().any(|addr| addr.port() == 3011));
+        });
+    }
+
+    /// Tests that `run_batched` rejects a batch size that wouldn't fit in a `u16` instead of
+    /// silently truncating it into a much smaller chunk size.
+    #[test]
+    #[should_panic(expected = "batch size must not exceed u16::MAX")]
+    fn test_run_batched_rejects_oversized_batch() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let scanner = Scanner::new(Duration::from_millis(200));
+            scanner.run_batched("127.0.0.1", 0, 10, 100_000).await;
+        });
+    }
+
+    /// Tests that `scan_detailed` classifies a listening port as open and a refused
+    /// connection as closed rather than collapsing both into a single boolean.
+    #[test]
+    fn test_scan_detailed_classifies_open_and_closed() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let _listener = start_mock_server(3007).await;
+            let ip = "127.0.0.1".to_string();
+            let results = Scanner::scan_detailed(ip).await;
+            let state_of = |port: u16| {
+                results
+                    .iter()
+                    .find(|(p, _)| *p == port)
+                    .map(|(_, state)| *state)
+            };
+            assert_eq!(state_of(3007), Some(PortState::Open));
+            assert_eq!(state_of(3008), Some(PortState::Closed));
+        });
+    }
+
+    /// Tests that `scan_top` only scans (and only reports on) the requested number of
+    /// common ports.
+    #[test]
+    fn test_scan_top_limits_to_n_ports() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let ip = "127.0.0.1".to_string();
+            let ports = Scanner::scan_top(ip, 5).await;
+            assert!(ports.len() <= 5);
+        });
+    }
+
+    /// Tests that `scan_hosts` returns one entry per host, each paired with its open ports.
+    #[test]
+    fn test_scan_hosts_returns_per_host_results() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let _listener = start_mock_server(3009).await;
+            let hosts = vec!["127.0.0.1".to_string()];
+            let results = Scanner::scan_hosts(hosts, DEFAULT_CONCURRENCY).await;
+            assert_eq!(results.len(), 1);
+            let (host, open) = &results[0];
+            assert_eq!(host, "127.0.0.1");
+            assert!(open.contains(&3009));
+        });
+    }
+
+    /// Tests that a `/30` CIDR block expands to its two usable host addresses, excluding the
+    /// network and broadcast addresses.
+    #[test]
+    fn test_cidr_expand_excludes_network_and_broadcast() {
+        let hosts = cidr::expand("192.168.1.0/30");
+        assert_eq!(
+            hosts,
+            vec![
+                "192.168.1.1".parse::<std::net::Ipv4Addr>().unwrap(),
+                "192.168.1.2".parse::<std::net::Ipv4Addr>().unwrap(),
+            ]
+        );
+    }
+
+    /// Tests that `expand` rejects a prefix shorter than its minimum instead of eagerly
+    /// materializing millions (or billions) of addresses.
+    #[test]
+    #[should_panic(expected = "prefix must be /16 or longer")]
+    fn test_cidr_expand_rejects_prefix_shorter_than_minimum() {
+        cidr::expand("10.0.0.0/8");
+    }
+
+    /// Tests that a `/0` prefix (`host_bits == 32`) doesn't overflow the host count shift,
+    /// instead covering the full address space. `expand` itself isn't called here since
+    /// materializing billions of `Ipv4Addr`s isn't practical in a unit test.
+    #[test]
+    fn test_cidr_host_range_prefix_zero_does_not_overflow() {
+        let (start, end) = cidr::host_range("0.0.0.0".parse().unwrap(), 0);
+        assert_eq!(start, 1);
+        assert_eq!(end, u32::MAX as u64);
+    }
+
+    /// Tests that `scan_proto` with `Protocol::Udp` reports some result for an unused port.
+    ///
+    /// Whether a closed UDP port surfaces as `Closed` depends on the network stack actually
+    /// delivering the ICMP port-unreachable error back to the socket before the timeout — some
+    /// sandboxed/containerized environments don't, in which case it's indistinguishable from
+    /// `OpenFiltered`. Both are valid classifications for "nothing is listening here".
+    #[test]
+    fn test_scan_proto_udp_reports_closed_or_open_filtered_port() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let ip = "127.0.0.1".to_string();
+            let results = Scanner::scan_proto(ip, Protocol::Udp).await;
+            let state = results
+                .iter()
+                .find(|(port, _)| *port == 3010)
+                .map(|(_, state)| *state);
+            assert!(matches!(
+                state,
+                Some(PortState::Closed) | Some(PortState::OpenFiltered)
+            ));
+        });
+    }
+
 }
 
 